@@ -29,7 +29,11 @@
 //! //! ```
 
 use serde::{Serialize, de::DeserializeOwned};
-use std::{fs::OpenOptions, io::Write, path::Path};
+use std::{
+    fs::OpenOptions,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -42,27 +46,166 @@ pub enum Error {
     ParTomlE(#[from] toml::ser::Error),
     #[error("parse toml error: {0}")]
     DesTomlE(#[from] toml::de::Error),
+    #[cfg(feature = "yaml")]
+    #[error("parse yaml error: {0}")]
+    YamlE(#[from] serde_yaml::Error),
+    #[cfg(feature = "ron")]
+    #[error("parse ron error: {0}")]
+    RonE(#[from] ron::Error),
+    #[cfg(feature = "ron")]
+    #[error("parse ron error: {0}")]
+    RonSpannedE(#[from] ron::error::SpannedError),
+    #[cfg(feature = "json5")]
+    #[error("parse json5 error: {0}")]
+    Json5E(#[from] json5::Error),
     #[error("extension does not exist.")]
     ExtensionDoesNotExist,
+    #[error("unknown format: `{0}`")]
+    UnknownFormat(String),
+    #[error("the {0} format is not enabled in this build")]
+    FormatNotEnabled(Format),
+    #[error("failed to deserialize at `{path}`: {source}")]
+    Deserialize {
+        path: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Format {
     Json,
     Toml,
+    Yaml,
+    Ron,
+    Json5,
 }
 
-fn path_to_format<P: AsRef<Path>>(path: P) -> Result<Format, Error> {
-    if let Some(v) = path.as_ref().extension().and_then(|f| f.to_str()) {
-        match v {
+impl Format {
+    /// Map a bare extension (without the leading dot) to a `Format`.
+    ///
+    /// Supported extensions are `json`, `toml`, and (when the corresponding feature is
+    /// enabled) `yaml`/`yml`, `ron`, and `json5`.
+    pub fn from_extension(ext: &str) -> Result<Self, Error> {
+        match ext {
             "json" => Ok(Format::Json),
             "toml" => Ok(Format::Toml),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Ok(Format::Yaml),
+            #[cfg(feature = "ron")]
+            "ron" => Ok(Format::Ron),
+            #[cfg(feature = "json5")]
+            "json5" => Ok(Format::Json5),
             _ => Err(Error::ExtensionDoesNotExist),
         }
+    }
+
+    /// Reports whether this format's backing Cargo feature was compiled into this build.
+    ///
+    /// `Json` and `Toml` are always enabled; the rest require their optional feature flag.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Format::Json | Format::Toml => true,
+            Format::Yaml => cfg!(feature = "yaml"),
+            Format::Ron => cfg!(feature = "ron"),
+            Format::Json5 => cfg!(feature = "json5"),
+        }
+    }
+
+    /// All formats this build actually supports, in a stable order.
+    pub fn all_enabled() -> &'static [Format] {
+        &[
+            Format::Json,
+            Format::Toml,
+            #[cfg(feature = "yaml")]
+            Format::Yaml,
+            #[cfg(feature = "ron")]
+            Format::Ron,
+            #[cfg(feature = "json5")]
+            Format::Json5,
+        ]
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Format::Json => "JSON",
+            Format::Toml => "TOML",
+            Format::Yaml => "YAML",
+            Format::Ron => "RON",
+            Format::Json5 => "JSON5",
+        };
+        f.write_str(s)
+    }
+}
+
+impl std::str::FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Format::Json),
+            "toml" => Ok(Format::Toml),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Ok(Format::Yaml),
+            #[cfg(feature = "ron")]
+            "ron" => Ok(Format::Ron),
+            #[cfg(feature = "json5")]
+            "json5" => Ok(Format::Json5),
+            other => Err(Error::UnknownFormat(other.to_string())),
+        }
+    }
+}
+
+fn path_to_format<P: AsRef<Path>>(path: P) -> Result<Format, Error> {
+    if let Some(v) = path.as_ref().extension().and_then(|f| f.to_str()) {
+        Format::from_extension(v)
     } else {
         Err(Error::ExtensionDoesNotExist)
     }
 }
 
+/// Output knobs for [`Storeable::save_with`] / [`Storeable::save_to_with`].
+///
+/// Defaults to pretty-printed output, matching the historical behavior of `save`.
+#[derive(Debug, Clone)]
+pub struct SaveOptions {
+    pretty: bool,
+    #[cfg(feature = "ron")]
+    ron_pretty_config: ron::ser::PrettyConfig,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            pretty: true,
+            #[cfg(feature = "ron")]
+            ron_pretty_config: ron::ser::PrettyConfig::default(),
+        }
+    }
+}
+
+impl SaveOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Controls whether the serialized output is pretty-printed (the default) or compact.
+    ///
+    /// Only affects formats that distinguish the two (currently `json`, `toml`, and `ron`).
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Overrides the `ron::ser::PrettyConfig` used when `pretty` is set and the format is RON.
+    #[cfg(feature = "ron")]
+    pub fn ron_pretty_config(mut self, config: ron::ser::PrettyConfig) -> Self {
+        self.ron_pretty_config = config;
+        self
+    }
+}
+
 pub trait Storeable: Serialize + DeserializeOwned + Sized {
     /// Save to file.
     ///
@@ -73,26 +216,157 @@ pub trait Storeable: Serialize + DeserializeOwned + Sized {
     ///
     /// # Returns
     /// * `Result<(), Error>` - A `Result` enum that indicates whether the operation was successful.
+    ///
+    /// # Crash safety
+    /// Serde output is streamed directly into a sibling temp file (`<file name>.tmp<pid>-<thread
+    /// id>`) in the same directory, flushed and `fsync`'d, and only then moved into place with
+    /// `std::fs::rename`, which is atomic on the same filesystem. The target path itself is
+    /// never opened for writing, so a serialization error (or a crash) only ever leaves a
+    /// partially-written temp file behind, and that temp file is removed on any error.
     fn save<P: AsRef<Path>>(&self, path: P, new_create: bool, format: Format) -> Result<(), Error> {
-        let s = match format {
-            Format::Json => serde_json::to_string_pretty(self)?,
-            Format::Toml => toml::to_string_pretty(self)?,
-        };
-        // let s = toml::to_string_pretty(self).map_err(Error::ParTomlE)?;
-        let mut f = OpenOptions::new()
-            .write(true)
-            .truncate(true)
-            .create(new_create)
-            .open(path)?;
-        // .map_err(Error::IoE)?;
-
-        f.write_all(s.as_bytes())?;
+        self.save_with(path, new_create, format, &SaveOptions::default())
+    }
+
+    /// Save to file, with explicit control over pretty-vs-compact output via [`SaveOptions`].
+    ///
+    /// Otherwise behaves exactly like [`Storeable::save`], including the atomic
+    /// temp-file-and-rename write strategy.
+    fn save_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        new_create: bool,
+        format: Format,
+        options: &SaveOptions,
+    ) -> Result<(), Error> {
+        let path = path.as_ref();
+        if !new_create && !path.exists() {
+            return Err(Error::IoE(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "file does not exist and new_create is false",
+            )));
+        }
+
+        let dir = path
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let file_name = path.file_name().ok_or(Error::ExtensionDoesNotExist)?;
+        // Thread id is appended alongside pid so concurrent saves to the same path from
+        // different threads of the same process don't race on the same temp file.
+        let tmp_path = dir.join(format!(
+            "{}.tmp{}-{:?}",
+            file_name.to_string_lossy(),
+            std::process::id(),
+            std::thread::current().id()
+        ));
+
+        let result = (|| -> Result<(), Error> {
+            let mut f = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&tmp_path)?;
+            self.save_to_with(&mut f, format, options)?;
+            f.sync_all()?;
+            std::fs::rename(&tmp_path, path)?;
+            Ok(())
+        })();
+
+        if result.is_err() {
+            let _ = std::fs::remove_file(&tmp_path);
+        }
+
+        result
+    }
+
+    /// Save to an arbitrary writer, e.g. an in-memory buffer, a socket, or stdout.
+    ///
+    /// Unlike [`Storeable::save`] this does not go through the filesystem at all, so there is
+    /// no atomicity to speak of: callers writing to a file directly are responsible for their
+    /// own crash safety.
+    ///
+    /// # Arguments
+    /// * `writer` - destination to stream the serialized value into.
+    /// * `format` - A `Format` enum that indicates the format to serialize into.
+    ///
+    /// # Returns
+    /// * `Result<(), Error>` - A `Result` enum that indicates whether the operation was successful.
+    fn save_to<W: Write>(&self, writer: W, format: Format) -> Result<(), Error> {
+        self.save_to_with(writer, format, &SaveOptions::default())
+    }
+
+    /// Save to an arbitrary writer, with explicit control over pretty-vs-compact output via
+    /// [`SaveOptions`].
+    fn save_to_with<W: Write>(
+        &self,
+        writer: W,
+        format: Format,
+        options: &SaveOptions,
+    ) -> Result<(), Error> {
+        let mut writer = BufWriter::new(writer);
+        match format {
+            Format::Json => {
+                if options.pretty {
+                    serde_json::to_writer_pretty(&mut writer, self)?
+                } else {
+                    serde_json::to_writer(&mut writer, self)?
+                }
+            }
+            Format::Toml => {
+                let s = if options.pretty {
+                    toml::to_string_pretty(self)?
+                } else {
+                    toml::to_string(self)?
+                };
+                writer.write_all(s.as_bytes())?
+            }
+            // serde_yaml has no separate compact mode.
+            Format::Yaml => {
+                #[cfg(not(feature = "yaml"))]
+                {
+                    return Err(Error::FormatNotEnabled(Format::Yaml));
+                }
+                #[cfg(feature = "yaml")]
+                {
+                    serde_yaml::to_writer(&mut writer, self)?
+                }
+            }
+            Format::Ron => {
+                #[cfg(not(feature = "ron"))]
+                {
+                    return Err(Error::FormatNotEnabled(Format::Ron));
+                }
+                #[cfg(feature = "ron")]
+                {
+                    let s = if options.pretty {
+                        ron::ser::to_string_pretty(self, options.ron_pretty_config.clone())?
+                    } else {
+                        ron::ser::to_string(self)?
+                    };
+                    writer.write_all(s.as_bytes())?
+                }
+            }
+            // json5 has no separate compact mode.
+            Format::Json5 => {
+                #[cfg(not(feature = "json5"))]
+                {
+                    return Err(Error::FormatNotEnabled(Format::Json5));
+                }
+                #[cfg(feature = "json5")]
+                {
+                    writer.write_all(json5::to_string(self)?.as_bytes())?
+                }
+            }
+        }
+        writer.flush()?;
         Ok(())
     }
 
     /// save to file by extension of `path`
     ///
-    /// supported extensions are `json` and `toml`.
+    /// supported extensions are `json`, `toml`, and (when the corresponding feature is
+    /// enabled) `yaml`/`yml`, `ron`, and `json5`. Always writes pretty-printed output, since
+    /// this is meant for human-edited files; use [`Storeable::save_with`] for compact output.
     ///
     /// # Arguments
     /// * `path` - path to the file.
@@ -113,18 +387,93 @@ pub trait Storeable: Serialize + DeserializeOwned + Sized {
     ///
     /// # Returns
     /// * `Result<Self, Error>` - A `Result` enum that indicates whether the operation was successful.
+    ///   On a malformed document this returns `Error::Deserialize`, which carries the dotted
+    ///   path of the offending field (e.g. `users[2].email`) alongside the underlying error.
     fn load<P: AsRef<Path>>(path: P, format: Format) -> Result<Self, Error> {
-        let content = std::fs::read_to_string(path)?;
-        // return deserialized date
+        let f = std::fs::File::open(path)?;
+        Self::load_from(f, format)
+    }
+
+    /// Load from an arbitrary reader, e.g. an in-memory buffer, stdin, or a pipe.
+    ///
+    /// # Arguments
+    /// * `reader` - source to stream the serialized value from.
+    /// * `format` - A `Format` enum that indicates the format to deserialize from.
+    ///
+    /// # Returns
+    /// * `Result<Self, Error>` - A `Result` enum that indicates whether the operation was successful.
+    ///   On a malformed document this returns `Error::Deserialize`, which carries the dotted
+    ///   path of the offending field (e.g. `users[2].email`) alongside the underlying error.
+    fn load_from<R: Read>(reader: R, format: Format) -> Result<Self, Error> {
+        let mut reader = BufReader::new(reader);
         Ok(match format {
-            Format::Json => serde_json::from_str::<Self>(&content)?,
-            Format::Toml => toml::from_str::<Self>(&content)?,
+            Format::Json => {
+                let de = &mut serde_json::Deserializer::from_reader(reader);
+                serde_path_to_error::deserialize(de).map_err(|e| Error::Deserialize {
+                    path: e.path().to_string(),
+                    source: Box::new(e.into_inner()),
+                })?
+            }
+            Format::Toml => {
+                let mut content = String::new();
+                reader.read_to_string(&mut content)?;
+                let de = toml::Deserializer::new(&content);
+                serde_path_to_error::deserialize(de).map_err(|e| Error::Deserialize {
+                    path: e.path().to_string(),
+                    source: Box::new(e.into_inner()),
+                })?
+            }
+            Format::Yaml => {
+                #[cfg(not(feature = "yaml"))]
+                {
+                    return Err(Error::FormatNotEnabled(Format::Yaml));
+                }
+                #[cfg(feature = "yaml")]
+                {
+                    let de = serde_yaml::Deserializer::from_reader(reader);
+                    serde_path_to_error::deserialize(de).map_err(|e| Error::Deserialize {
+                        path: e.path().to_string(),
+                        source: Box::new(e.into_inner()),
+                    })?
+                }
+            }
+            Format::Ron => {
+                #[cfg(not(feature = "ron"))]
+                {
+                    return Err(Error::FormatNotEnabled(Format::Ron));
+                }
+                #[cfg(feature = "ron")]
+                {
+                    let mut content = String::new();
+                    reader.read_to_string(&mut content)?;
+                    let mut de = ron::Deserializer::from_str(&content)?;
+                    serde_path_to_error::deserialize(&mut de).map_err(|e| Error::Deserialize {
+                        path: e.path().to_string(),
+                        source: Box::new(e.into_inner()),
+                    })?
+                }
+            }
+            // json5 does not expose a public `serde::Deserializer`, so path-to-error
+            // reporting is not available for this format.
+            Format::Json5 => {
+                #[cfg(not(feature = "json5"))]
+                {
+                    return Err(Error::FormatNotEnabled(Format::Json5));
+                }
+                #[cfg(feature = "json5")]
+                {
+                    let mut content = String::new();
+                    reader.read_to_string(&mut content)?;
+                    json5::from_str::<Self>(&content)?
+                }
+            }
         })
     }
 
     /// load from file by extension of `path`
     ///
-    /// supported extensions are `json` and `toml`
+    /// supported extensions are `json`, `toml`, and (when the corresponding feature is
+    /// enabled) `yaml`/`yml`, `ron`, and `json5`
     ///
     /// # Arguments
     ///
@@ -140,9 +489,9 @@ pub trait Storeable: Serialize + DeserializeOwned + Sized {
 
 #[cfg(test)]
 mod tests {
-    use crate::Storeable;
+    use crate::{Error, Format, SaveOptions, Storeable};
     use serde::{Deserialize, Serialize};
-    use std::path::PathBuf;
+    use std::{path::PathBuf, str::FromStr};
 
     #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
     struct User {
@@ -192,4 +541,79 @@ mod tests {
             Err(_) => assert!(loaded.is_ok()),
         }
     }
+
+    #[test]
+    fn load_from_reports_the_offending_field_path() {
+        let malformed = b"name = \"Alice\"\nemail = 123\n";
+        let err = User::load_from(&malformed[..], Format::Toml).unwrap_err();
+        match err {
+            Error::Deserialize { path, .. } => assert_eq!(path, "email"),
+            other => panic!("expected Error::Deserialize, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn format_display_and_fromstr_round_trip() {
+        for fmt in Format::all_enabled() {
+            let s = fmt.to_string();
+            assert_eq!(Format::from_str(&s).unwrap(), *fmt);
+        }
+    }
+
+    #[test]
+    fn format_from_str_is_case_insensitive() {
+        assert_eq!(Format::from_str("json").unwrap(), Format::Json);
+        assert_eq!(Format::from_str("JSON").unwrap(), Format::Json);
+        assert_eq!(Format::from_str("ToMl").unwrap(), Format::Toml);
+    }
+
+    #[test]
+    fn format_from_str_rejects_unknown() {
+        match Format::from_str("xml") {
+            Err(Error::UnknownFormat(s)) => assert_eq!(s, "xml"),
+            other => panic!("expected Error::UnknownFormat, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn format_from_extension_rejects_unknown() {
+        assert!(matches!(
+            Format::from_extension("exe"),
+            Err(Error::ExtensionDoesNotExist)
+        ));
+    }
+
+    #[test]
+    fn format_is_enabled_matches_compiled_features() {
+        assert!(Format::Json.is_enabled());
+        assert!(Format::Toml.is_enabled());
+        assert_eq!(Format::Yaml.is_enabled(), cfg!(feature = "yaml"));
+        assert_eq!(Format::Ron.is_enabled(), cfg!(feature = "ron"));
+        assert_eq!(Format::Json5.is_enabled(), cfg!(feature = "json5"));
+    }
+
+    #[test]
+    fn format_all_enabled_contains_only_enabled_formats() {
+        assert!(Format::all_enabled().iter().all(Format::is_enabled));
+        assert!(Format::all_enabled().contains(&Format::Json));
+        assert!(Format::all_enabled().contains(&Format::Toml));
+    }
+
+    #[test]
+    fn save_to_with_respects_pretty_option() {
+        let user = User {
+            name: "Alice".to_string(),
+            email: "alice@alice.com".to_string(),
+        };
+
+        let mut pretty = Vec::new();
+        user.save_to_with(&mut pretty, Format::Json, &SaveOptions::default())
+            .unwrap();
+        assert!(String::from_utf8(pretty).unwrap().contains('\n'));
+
+        let mut compact = Vec::new();
+        user.save_to_with(&mut compact, Format::Json, &SaveOptions::new().pretty(false))
+            .unwrap();
+        assert!(!String::from_utf8(compact).unwrap().contains('\n'));
+    }
 }